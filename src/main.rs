@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Result};
 use clap::Parser;
+use clap_complete::Shell;
 use log::LevelFilter;
 use ramen;
 use std::io::{self, Read};
@@ -17,6 +18,10 @@ struct Cli {
     #[arg(short, long, value_name = "DEBUG")]
     debug: bool,
 
+    /// Generate a shell completion script for the spec instead of parsing.
+    #[arg(long, value_name = "SHELL")]
+    completion: Option<Shell>,
+
     /// The arguments to parse. Passed as the last argument, after a "--".
     /// Usually it's "$@" in the bash script. e.g.
     ///
@@ -33,6 +38,17 @@ fn main() -> Result<()> {
         LevelFilter::Warn
     });
 
+    if let Some(shell) = cli.completion {
+        let spec_from_pipe = read_spec_from_stdin()?;
+        let spec = if spec_from_pipe.is_empty() {
+            cli.spec.unwrap_or_default()
+        } else {
+            spec_from_pipe
+        };
+        print!("{}", ramen::generate_completion(&spec, shell)?);
+        return Ok(());
+    }
+
     let spec_from_pipe = read_spec_from_stdin()?;
     let spec_from_arg = cli.spec.unwrap_or_default();
 
@@ -51,9 +67,25 @@ fn main() -> Result<()> {
     // to parse the optstring, and it treats the first element from the given
     // VEC as the name of the program, we insert a dummy value here to optstring.
     cli.optstring.insert(0, "PROG".to_string());
-    let output = ramen::parse(&spec, &cli.optstring)?;
-    println!("{}", output);
-    Ok(())
+    match ramen::parse(&spec, &cli.optstring) {
+        Ok(output) => {
+            println!("{}", output);
+            Ok(())
+        }
+        Err(e) => {
+            // Print a safe assignment instead of letting clap's error dump
+            // hit stderr, so a calling `eval "$( ramen ... )"` can branch on
+            // __YOPTS_ERROR rather than choking on unparsed text.
+            println!("__YOPTS_ERROR={}", shell_quote(&e.to_string()));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Single-quote `value`, escaping any embedded single quotes, so it can be
+/// safely assigned inside `eval "$( ramen ... )"`.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
 }
 
 /// Read data from STDIN if provided.
@@ -1,4 +1,5 @@
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgMatches, Command, ValueHint};
+use clap_complete::Shell;
 use log::debug;
 use once_cell::sync::Lazy;
 use regex::{Match, Regex};
@@ -39,8 +40,14 @@ pub enum Error {
     #[error("missing argument name (key: args[].name)")]
     MissingArgumentName,
 
+    #[error("missing group name (key: groups[].name)")]
+    MissingGroupName,
+
     #[error(transparent)]
     Format(#[from] std::fmt::Error),
+
+    #[error("{0}")]
+    InvalidOptstring(String),
 }
 
 pub struct ArgumentParser {
@@ -85,6 +92,12 @@ impl ArgumentParser {
         self.doc["about"].as_str().unwrap_or_default()
     }
 
+    /// Whether emitted values are single-quoted for safe `eval` consumption.
+    /// Defaults to `true`; set `quote: false` to opt out and emit raw values.
+    pub fn quote(&self) -> bool {
+        self.doc["quote"].as_bool().unwrap_or(true)
+    }
+
     /// Create a list of Argument instance by parsing the `args` definitions.
     pub fn args(&self) -> Vec<Argument> {
         self.doc["args"]
@@ -93,6 +106,15 @@ impl ArgumentParser {
             .unwrap_or_default()
     }
 
+    /// Create a list of ArgGroup instances by parsing the top-level `groups`
+    /// definitions.
+    pub fn groups(&self) -> Vec<ArgGroup> {
+        self.doc["groups"]
+            .as_vec()
+            .map(|vec| vec.iter().map(|item| ArgGroup::new(item)).collect())
+            .unwrap_or_default()
+    }
+
     pub fn build_clap_command(&self) -> Result<Command> {
         let mut command = Command::new(self.program().to_owned()).about(self.about().to_owned());
 
@@ -110,14 +132,49 @@ impl ArgumentParser {
             }
             if arg.is_flag() {
                 clap_arg = clap_arg.action(clap::ArgAction::SetTrue);
+            } else if arg.is_multiple() {
+                clap_arg = clap_arg.action(clap::ArgAction::Append);
             }
             if let Some(help) = arg.help() {
                 clap_arg = clap_arg.help(help.to_string());
             }
+            if let Some(choices) = arg.select() {
+                let choices: Vec<String> = choices.into_iter().map(str::to_string).collect();
+                clap_arg = clap_arg.value_parser(clap::builder::PossibleValuesParser::new(choices));
+            } else if arg.is_number() {
+                if arg.is_float() {
+                    clap_arg = clap_arg.value_parser(ranged_f64_value_parser(arg.min_f64(), arg.max_f64()));
+                } else {
+                    let range = arg.min().unwrap_or(i64::MIN)..=arg.max().unwrap_or(i64::MAX);
+                    clap_arg = clap_arg.value_parser(clap::value_parser!(i64).range(range));
+                }
+            }
 
-            clap_arg = clap_arg.required(is_positional);
+            if let Some(hint) = arg.value_hint() {
+                clap_arg = clap_arg.value_hint(hint);
+            }
+            if let Some(conflicts) = arg.conflicts_with() {
+                let conflicts: Vec<String> = conflicts.into_iter().map(str::to_string).collect();
+                clap_arg = clap_arg.conflicts_with_all(conflicts);
+            }
+            if let Some(requires) = arg.requires() {
+                let requires: Vec<String> = requires.into_iter().map(str::to_string).collect();
+                clap_arg = clap_arg.requires_all(requires);
+            }
+
+            clap_arg = clap_arg.required(arg.required().unwrap_or(is_positional));
             command = command.arg(clap_arg);
         }
+
+        for group in self.groups().iter() {
+            let args: Vec<String> = group.args().into_iter().map(str::to_string).collect();
+            let clap_group = clap::ArgGroup::new(group.name()?)
+                .args(args)
+                .required(group.required())
+                .multiple(group.multiple());
+            command = command.group(clap_group);
+        }
+
         command.build();
         Ok(command)
     }
@@ -200,7 +257,7 @@ impl<'a> Argument<'a> {
             .ok_or(Error::MissingArgumentName)
     }
 
-    /// The type of the argument, can be string, number, boolean.
+    /// The type of the argument, can be string, number, float, boolean, list.
     pub fn typ(&self) -> &str {
         self.doc["type"].as_str().unwrap_or("string")
     }
@@ -209,6 +266,46 @@ impl<'a> Argument<'a> {
         ["bool", "boolean"].contains(&self.typ())
     }
 
+    pub fn is_number(&self) -> bool {
+        ["number", "float"].contains(&self.typ())
+    }
+
+    /// Whether this argument accepts repeated occurrences, collected into a
+    /// shell array on output (key: multiple, or type: list).
+    pub fn is_multiple(&self) -> bool {
+        self.typ() == "list" || self.doc["multiple"].as_bool().unwrap_or(false)
+    }
+
+    /// Whether the numeric value should be parsed as a float rather than
+    /// an integer (key: `type: float`).
+    pub fn is_float(&self) -> bool {
+        self.typ() == "float"
+    }
+
+    /// The lower bound of an integer `number` argument (key: min), as `i64`.
+    pub fn min(&self) -> Option<i64> {
+        self.doc["min"].as_i64()
+    }
+
+    /// The upper bound of an integer `number` argument (key: max), as `i64`.
+    pub fn max(&self) -> Option<i64> {
+        self.doc["max"].as_i64()
+    }
+
+    /// The lower bound of a `float` argument (key: min), as `f64`.
+    pub fn min_f64(&self) -> Option<f64> {
+        self.doc["min"]
+            .as_f64()
+            .or_else(|| self.doc["min"].as_i64().map(|v| v as f64))
+    }
+
+    /// The upper bound of a `float` argument (key: max), as `f64`.
+    pub fn max_f64(&self) -> Option<f64> {
+        self.doc["max"]
+            .as_f64()
+            .or_else(|| self.doc["max"].as_i64().map(|v| v as f64))
+    }
+
     /// The default value of the argument on absent.
     pub fn default(&self) -> &str {
         self.doc["default"].as_str().unwrap_or_default()
@@ -223,6 +320,75 @@ impl<'a> Argument<'a> {
             .as_vec()
             .map(|x| x.iter().map(|v| v.as_str().unwrap_or_default()).collect())
     }
+
+    /// Explicit override of whether the argument is required (key: required).
+    /// When absent, positional arguments fall back to being required.
+    pub fn required(&self) -> Option<bool> {
+        self.doc["required"].as_bool()
+    }
+
+    /// Other argument ids that must NOT be given together with this one
+    /// (key: conflicts_with).
+    pub fn conflicts_with(&self) -> Option<Vec<&str>> {
+        self.doc["conflicts_with"]
+            .as_vec()
+            .map(|x| x.iter().map(|v| v.as_str().unwrap_or_default()).collect())
+    }
+
+    /// Other argument ids that must be given together with this one
+    /// (key: requires).
+    pub fn requires(&self) -> Option<Vec<&str>> {
+        self.doc["requires"]
+            .as_vec()
+            .map(|x| x.iter().map(|v| v.as_str().unwrap_or_default()).collect())
+    }
+
+    /// A hint for shell completion engines about the kind of value expected
+    /// (key: value_hint), ex. `file_path`, `dir_path`, `hostname`.
+    pub fn value_hint(&self) -> Option<ValueHint> {
+        match self.doc["value_hint"].as_str()? {
+            "file_path" => Some(ValueHint::FilePath),
+            "dir_path" => Some(ValueHint::DirPath),
+            "hostname" => Some(ValueHint::Hostname),
+            _ => None,
+        }
+    }
+}
+
+/// Represents a [`clap::ArgGroup`], describing a mutually-exclusive or
+/// required-one relationship between a set of arguments.
+#[derive(Debug, Clone)]
+pub struct ArgGroup<'a> {
+    doc: &'a Yaml,
+}
+
+impl<'a> ArgGroup<'a> {
+    pub fn new(doc: &'a Yaml) -> Self {
+        Self { doc }
+    }
+
+    pub fn name(&self) -> Result<String> {
+        self.doc["name"]
+            .as_str()
+            .map(|x| x.to_string())
+            .ok_or(Error::MissingGroupName)
+    }
+
+    pub fn args(&self) -> Vec<&str> {
+        self.doc["args"]
+            .as_vec()
+            .map(|x| x.iter().map(|v| v.as_str().unwrap_or_default()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn required(&self) -> bool {
+        self.doc["required"].as_bool().unwrap_or(false)
+    }
+
+    /// Whether more than one argument in the group may be given at once.
+    pub fn multiple(&self) -> bool {
+        self.doc["multiple"].as_bool().unwrap_or(false)
+    }
 }
 
 pub fn parse(spec_yaml: &str, optstring: &[String]) -> Result<String> {
@@ -236,10 +402,52 @@ pub fn parse(spec_yaml: &str, optstring: &[String]) -> Result<String> {
     let optstring = normalize_optstring(optstring);
     // Let the command parse optstring. And use the matches to compose the eval script.
     debug!(target: "yopts::parse", "OPTSTRING: {optstring:?}");
-    let matches = command.get_matches_from(optstring);
+    let matches = command
+        .try_get_matches_from(optstring)
+        .map_err(describe_clap_error)?;
     compose_shell_script(&parser, &matches)
 }
 
+/// Reduce a [`clap::Error`] to its message body, stripped of the `error: `
+/// prefix and the trailing `Usage:`/`For more information` block, so callers
+/// get a short, eval-safe description instead of clap's full help/usage dump
+/// (as in clap's `Error::with_description` pattern). Unlike taking only the
+/// first line, this keeps multi-line messages intact (e.g. the arguments
+/// clap lists under "the following required arguments were not provided:").
+fn describe_clap_error(err: clap::Error) -> Error {
+    let rendered = err.render().to_string();
+    let body = rendered
+        .split("\n\nUsage:")
+        .next()
+        .unwrap_or(&rendered)
+        .split("\n\nFor more information")
+        .next()
+        .unwrap_or(&rendered)
+        .trim_start_matches("error: ");
+    let description = body
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Error::InvalidOptstring(description)
+}
+
+/// Build the shell command for the given spec and render a completion
+/// script for it, ex. for use as `eval "$( ramen --completion bash "$program" )"`.
+pub fn generate_completion(spec_yaml: &str, shell: Shell) -> Result<String> {
+    let mut docs = YamlLoader::load_from_str(spec_yaml)?;
+    validate_root_docs(&docs)?;
+
+    let parser = ArgumentParser::new(docs.remove(0))?;
+    let mut command = parser.build_clap_command()?;
+
+    let name = command.get_name().to_string();
+    let mut buf: Vec<u8> = Vec::new();
+    clap_complete::generate(shell, &mut command, name, &mut buf);
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
 /// Add some salts to the given optstring.
 /// Since we will be calling clap::Command::get_matches_from(VEC) API
 /// to parse the optstring, and it treats the first element from the given
@@ -254,11 +462,12 @@ fn normalize_optstring(optstring: &[String]) -> Vec<String> {
 
 fn compose_shell_script(parser: &ArgumentParser, matches: &ArgMatches) -> Result<String> {
     let mut script = String::with_capacity(256);
+    let quote = parser.quote();
 
     for arg in parser.args().iter() {
         let key = arg.id()?;
         let prefix = parser.output_prefix();
-        let output_key = format!("{prefix}{key}");
+        let output_key = shell_identifier(&format!("{prefix}{key}"));
 
         debug!(
             target: "yopts::compose_shell_script",
@@ -268,10 +477,36 @@ fn compose_shell_script(parser: &ArgumentParser, matches: &ArgMatches) -> Result
         if arg.is_flag() {
             let flag = matches.get_flag(&key);
             writeln!(&mut script, "{}={}", output_key, flag)?;
+        } else if arg.is_multiple() && arg.select().is_none() && arg.is_number() && arg.is_float() {
+            let values: Vec<String> = matches
+                .get_many::<f64>(&key)
+                .map(|vals| vals.map(|v| v.to_string()).collect())
+                .unwrap_or_default();
+            writeln!(&mut script, "{}=({})", output_key, values.join(" "))?;
+        } else if arg.is_multiple() && arg.select().is_none() && arg.is_number() {
+            let values: Vec<String> = matches
+                .get_many::<i64>(&key)
+                .map(|vals| vals.map(|v| v.to_string()).collect())
+                .unwrap_or_default();
+            writeln!(&mut script, "{}=({})", output_key, values.join(" "))?;
+        } else if arg.is_multiple() {
+            let values: Vec<String> = matches
+                .get_many::<String>(&key)
+                .map(|vals| vals.map(|v| maybe_quote(v, quote)).collect())
+                .unwrap_or_default();
+            writeln!(&mut script, "{}=({})", output_key, values.join(" "))?;
+        } else if arg.select().is_none() && arg.is_number() && arg.is_float() {
+            if let Some(given_value) = matches.get_one::<f64>(&key) {
+                writeln!(&mut script, "{}={}", output_key, given_value)?;
+            }
+        } else if arg.select().is_none() && arg.is_number() {
+            if let Some(given_value) = matches.get_one::<i64>(&key) {
+                writeln!(&mut script, "{}={}", output_key, given_value)?;
+            }
         } else {
             let value = matches.get_one::<String>(&key);
             if let Some(given_value) = value {
-                writeln!(&mut script, "{}={}", output_key, given_value)?;
+                writeln!(&mut script, "{}={}", output_key, maybe_quote(given_value, quote))?;
             }
         }
     }
@@ -279,6 +514,23 @@ fn compose_shell_script(parser: &ArgumentParser, matches: &ArgMatches) -> Result
     Ok(script)
 }
 
+/// Single-quote `value` for safe `eval` consumption, escaping any embedded
+/// single quotes, unless `quote` is `false` in which case it is emitted raw.
+fn maybe_quote(value: &str, quote: bool) -> String {
+    if !quote {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Sanitize a variable name so it can only ever produce a valid shell
+/// identifier, replacing any character that isn't alphanumeric or `_`.
+fn shell_identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
 fn validate_root_docs(docs: &Vec<Yaml>) -> Result<()> {
     if docs.len() == 0 {
         return Err(Error::NoDocs);
@@ -289,6 +541,28 @@ fn validate_root_docs(docs: &Vec<Yaml>) -> Result<()> {
     Ok(())
 }
 
+/// Build a `f64` value parser that also rejects values outside of `min`/`max`,
+/// clap's `value_parser!` macro has no ranged float parser of its own.
+fn ranged_f64_value_parser(
+    min: Option<f64>,
+    max: Option<f64>,
+) -> impl Fn(&str) -> std::result::Result<f64, String> + Clone + Send + Sync + 'static {
+    move |s: &str| {
+        let value: f64 = s.parse().map_err(|_| format!("'{s}' isn't a valid float value"))?;
+        if let Some(min) = min {
+            if value < min {
+                return Err(format!("{value} is less than the minimum of {min}"));
+            }
+        }
+        if let Some(max) = max {
+            if value > max {
+                return Err(format!("{value} is greater than the maximum of {max}"));
+            }
+        }
+        Ok(value)
+    }
+}
+
 /// Extract the short and long name from the given text when it complies to the pattern `-s/--long`.
 fn extract_short_long_name(haystack: &str) -> (Option<String>, Option<String>) {
     let convert = |m: Option<Match<'_>>| m.map(|x| x.as_str().to_string());
@@ -24,16 +24,34 @@ fn test_parse_only_names() {
 
     expect_output(
         vec![
-            "SRC=/path/to/src",
-            "DST=/path/to/dst",
-            "verbose=true",
-            "threads=8",
-            "protocol=s3",
+            "SRC='/path/to/src'",
+            "DST='/path/to/dst'",
+            "verbose='true'",
+            "threads='8'",
+            "protocol='s3'",
         ],
         &output,
     )
 }
 
+#[test]
+fn test_parse_select_rejects_out_of_set_value() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: protocol
+        long: --protocol
+        select: [s3, scp, ftp]
+    "#;
+    let optstring: Vec<String> = vec!["--protocol", "rsync"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+
+    assert!(yopts::parse(PROGRAM, &optstring).is_err());
+}
+
 #[test]
 fn test_parse_boolean_flags() {
     const PROGRAM: &str = r#"
@@ -65,16 +83,297 @@ fn test_parse_boolean_flags() {
 
     expect_output(
         vec![
-            "SRC=/path/to/src",
-            "DST=/path/to/dst",
+            "SRC='/path/to/src'",
+            "DST='/path/to/dst'",
             "verbose=true",
-            "threads=4",
-            "protocol=scp",
+            "threads='4'",
+            "protocol='scp'",
         ],
         &output,
     )
 }
 
+#[test]
+fn test_parse_number_rejects_out_of_range_value() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: threads
+        long: --threads
+        type: number
+        min: 1
+        max: 64
+    "#;
+    let optstring: Vec<String> = vec!["--threads", "9999"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+
+    assert!(yopts::parse(PROGRAM, &optstring).is_err());
+}
+
+#[test]
+fn test_parse_float_value() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: rate
+        long: --rate
+        type: float
+        min: 0
+        max: 10
+    "#;
+    let optstring: Vec<String> = vec!["--rate", "1.5"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+    let output = yopts::parse(PROGRAM, &optstring).unwrap();
+
+    expect_output(vec!["rate=1.5"], &output)
+}
+
+#[test]
+fn test_parse_float_rejects_out_of_range_value() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: rate
+        long: --rate
+        type: float
+        min: 0
+        max: 10
+    "#;
+    let optstring: Vec<String> = vec!["--rate", "9999.0"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+
+    assert!(yopts::parse(PROGRAM, &optstring).is_err());
+}
+
+#[test]
+fn test_parse_multiple_values() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: include
+        short: -I
+        long: --include
+        multiple: true
+    "#;
+    let optstring: Vec<String> = vec!["-I", "foo", "-I", "bar"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+    let output = yopts::parse(PROGRAM, &optstring).unwrap();
+
+    expect_output(vec!["include=('foo' 'bar')"], &output)
+}
+
+#[test]
+fn test_parse_multiple_number_values() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: ports
+        long: --ports
+        type: number
+        multiple: true
+    "#;
+    let optstring: Vec<String> = vec!["--ports", "80", "--ports", "443"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+    let output = yopts::parse(PROGRAM, &optstring).unwrap();
+
+    expect_output(vec!["ports=(80 443)"], &output)
+}
+
+#[test]
+fn test_parse_conflicts_with_rejects_combined_args() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: json
+        long: --json
+        type: boolean
+        conflicts_with: [yaml]
+      - name: yaml
+        long: --yaml
+        type: boolean
+    "#;
+    let optstring: Vec<String> = vec!["--json", "--yaml"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+
+    assert!(yopts::parse(PROGRAM, &optstring).is_err());
+}
+
+#[test]
+fn test_parse_requires_rejects_missing_dependency() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: output
+        long: --output
+        requires: [format]
+      - name: format
+        long: --format
+    "#;
+    let optstring: Vec<String> = vec!["--output", "out.txt"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+
+    assert!(yopts::parse(PROGRAM, &optstring).is_err());
+}
+
+#[test]
+fn test_parse_groups_require_exactly_one() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: json
+        long: --json
+        type: boolean
+      - name: yaml
+        long: --yaml
+        type: boolean
+    groups:
+      - name: format
+        args: [json, yaml]
+        required: true
+        multiple: false
+    "#;
+    let optstring: Vec<String> = vec!["--json", "--yaml"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+
+    assert!(yopts::parse(PROGRAM, &optstring).is_err());
+}
+
+#[test]
+fn test_parse_groups_required_names_its_members_when_none_given() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: json
+        long: --json
+        type: boolean
+      - name: yaml
+        long: --yaml
+        type: boolean
+    groups:
+      - name: format
+        args: [json, yaml]
+        required: true
+    "#;
+    let optstring: Vec<String> = vec![].iter().map(|&x: &&str| x.to_string()).collect();
+
+    let err = yopts::parse(PROGRAM, &optstring).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("json"), "error message was: {message}");
+    assert!(message.contains("yaml"), "error message was: {message}");
+}
+
+#[test]
+fn test_parse_escapes_shell_metacharacters() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args: [NAME]
+    "#;
+    let optstring: Vec<String> = vec!["$(rm -rf ~); it's"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+    let output = yopts::parse(PROGRAM, &optstring).unwrap();
+
+    expect_output(vec![r#"NAME='$(rm -rf ~); it'\''s'"#], &output)
+}
+
+#[test]
+fn test_parse_quote_false_emits_raw_values() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    quote: false
+    args: [NAME]
+    "#;
+    let optstring: Vec<String> = vec!["raw value"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+    let output = yopts::parse(PROGRAM, &optstring).unwrap();
+
+    expect_output(vec!["NAME=raw value"], &output)
+}
+
+#[test]
+fn test_generate_completion_smoke() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: src
+        long: --src
+        value_hint: file_path
+    "#;
+    let output = yopts::generate_completion(PROGRAM, clap_complete::Shell::Bash).unwrap();
+
+    assert!(output.contains("upload"));
+    assert!(output.contains("--src"));
+}
+
+#[test]
+fn test_parse_invalid_optstring_does_not_panic() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args:
+      - name: threads
+        long: --threads
+        type: number
+    "#;
+    let optstring: Vec<String> = vec!["--threads", "abc"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+
+    assert!(yopts::parse(PROGRAM, &optstring).is_err());
+}
+
+#[test]
+fn test_parse_missing_required_argument_names_it_in_the_error() {
+    const PROGRAM: &str = r#"
+    version: "1.0.0"
+    program: upload
+    args: [SRC, DST]
+    "#;
+    let optstring: Vec<String> = vec!["/path/to/src"]
+        .iter()
+        .map(|&x| x.to_string())
+        .collect();
+
+    let err = yopts::parse(PROGRAM, &optstring).unwrap_err();
+    let message = err.to_string();
+    assert!(
+        message.contains("DST"),
+        "error message dropped the missing argument name: {message}"
+    );
+}
+
 fn expect_output(expected_lines: Vec<&str>, got_output: &str) {
     let mut sorted_expected_lines = expected_lines.clone();
     sorted_expected_lines.sort();